@@ -4,15 +4,22 @@ use std::collections::HashMap;
 use crate::native::hasher::hash;
 use crate::native::utils::Normalize;
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
 use rayon::prelude::*;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
 
 use crate::native::logger::enable_logger;
 use crate::native::project_graph::utils::{find_project_for_path, ProjectRootMappings};
 use crate::native::types::FileData;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use parking_lot::{Condvar, Mutex};
 use tracing::{trace, warn};
 
@@ -22,37 +29,191 @@ use crate::native::workspace::types::{
 };
 use crate::native::workspace::{config_files, workspace_files};
 
+/// Raw filesystem events are coalesced into a single batch once no new event
+/// has arrived for this long. Keeps a burst of writes (editors often touch a
+/// file several times when saving) from fanning out into many rehash passes.
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(75);
+
+/// On-disk file-hash index, relative to the workspace root. Bumping either the
+/// layout version or the hashing-algorithm tag invalidates the whole cache so a
+/// stale header can never hand back a hash computed with a different scheme.
+const CACHE_VERSION: u32 = 1;
+const HASH_ALGORITHM: &str = "xxh3";
+const CACHE_PATH: &str = ".nx/cache/file-hashes.bin";
+
+/// One cached hash plus the cheap metadata used to decide whether it is still
+/// valid. `mtime_ns`/`size` together stand in for "did the bytes change" without
+/// having to read the file back.
+#[derive(Serialize, Deserialize, Clone)]
+struct CacheRecord {
+    mtime_ns: u128,
+    size: u64,
+    hash: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct FileHashCache {
+    version: u32,
+    algorithm: String,
+    records: HashMap<String, CacheRecord>,
+}
+
+impl FileHashCache {
+    fn empty() -> Self {
+        FileHashCache {
+            version: CACHE_VERSION,
+            algorithm: HASH_ALGORITHM.to_string(),
+            records: HashMap::new(),
+        }
+    }
+
+    /// Load the cache from `.nx/cache/`, discarding it wholesale if the file is
+    /// missing, unreadable, or tagged with an incompatible version/algorithm.
+    fn load(workspace_root: &Path) -> Self {
+        let Ok(bytes) = std::fs::read(workspace_root.join(CACHE_PATH)) else {
+            return FileHashCache::empty();
+        };
+        match bincode::deserialize::<FileHashCache>(&bytes) {
+            Ok(cache) if cache.version == CACHE_VERSION && cache.algorithm == HASH_ALGORITHM => {
+                cache
+            }
+            _ => {
+                trace!("file-hash cache was incompatible, discarding");
+                FileHashCache::empty()
+            }
+        }
+    }
+
+    /// Return the cached hash for `relative` if the stored mtime and size still
+    /// match the file on disk.
+    fn get_valid(&self, relative: &str, metadata: &std::fs::Metadata) -> Option<String> {
+        let record = self.records.get(relative)?;
+        (record.size == metadata.len() && record.mtime_ns == mtime_ns(metadata))
+            .then(|| record.hash.clone())
+    }
+
+    fn insert(&mut self, relative: String, metadata: &std::fs::Metadata, hash: String) {
+        self.records.insert(
+            relative,
+            CacheRecord {
+                mtime_ns: mtime_ns(metadata),
+                size: metadata.len(),
+                hash,
+            },
+        );
+    }
+
+    /// Atomically replace the on-disk cache: serialize to a temp file in the
+    /// same directory, then rename over the target so a crash mid-write leaves
+    /// the previous cache intact rather than a truncated one.
+    fn save(&self, workspace_root: &Path) {
+        let target = workspace_root.join(CACHE_PATH);
+        let Some(dir) = target.parent() else {
+            return;
+        };
+        if let Err(err) = std::fs::create_dir_all(dir) {
+            trace!("could not create cache dir: {err}");
+            return;
+        }
+        let Ok(bytes) = bincode::serialize(self) else {
+            return;
+        };
+        let tmp = target.with_extension("bin.tmp");
+        if std::fs::write(&tmp, bytes).is_ok() {
+            if let Err(err) = std::fs::rename(&tmp, &target) {
+                trace!("could not persist file-hash cache: {err}");
+            }
+        }
+    }
+}
+
+/// File modification time expressed as nanoseconds since the Unix epoch, or 0
+/// on platforms/filesystems that cannot report one.
+fn mtime_ns(metadata: &std::fs::Metadata) -> u128 {
+    metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
 #[napi]
 pub struct WorkspaceContext {
     pub workspace_root: String,
     workspace_root_path: PathBuf,
     files_worker: FilesWorker,
+    watcher: Mutex<Option<RecommendedWatcher>>,
+    /// In-memory file contents layered on top of the on-disk state, keyed by
+    /// normalized workspace-relative path. Mirrors rust-analyzer's VFS overlay:
+    /// unsaved editor buffers participate in queries without touching disk.
+    overlay: Mutex<HashMap<String, Vec<u8>>>,
 }
 
-type Files = Vec<(PathBuf, String)>;
-struct FilesWorker(Option<Arc<(Mutex<Files>, Condvar)>>);
+/// In-memory file records: path plus the cheap metadata (`mtime_ns`, `size`)
+/// that lets `update_files` skip re-reading byte-identical files, plus the hash.
+/// The metadata never leaves this module — callers only ever see `FileData`.
+type Files = Vec<(PathBuf, u128, u64, String)>;
+#[derive(Clone)]
+struct FilesWorker {
+    files: Option<Arc<(Mutex<Files>, Condvar)>>,
+    /// Bumped on every mutation so an outstanding snapshot can tell whether the
+    /// world has moved on underneath it.
+    generation: Arc<AtomicU64>,
+    /// Flipped to `true` once the initial `gather_files` walk has populated the
+    /// store. Callers that must not race the walk (e.g. the watcher drain
+    /// thread) wait on the `Condvar` until this is set.
+    initialized: Arc<AtomicBool>,
+}
 impl FilesWorker {
     fn gather_files(workspace_root: &Path) -> Self {
+        let generation = Arc::new(AtomicU64::new(0));
+        let initialized = Arc::new(AtomicBool::new(false));
         if !workspace_root.exists() {
             warn!(
                 "workspace root does not exist: {}",
                 workspace_root.display()
             );
-            return FilesWorker(None);
+            return FilesWorker {
+                files: None,
+                generation,
+                initialized,
+            };
         }
 
         let files_lock = Arc::new((Mutex::new(Vec::new()), Condvar::new()));
         let files_lock_clone = Arc::clone(&files_lock);
+        let initialized_clone = Arc::clone(&initialized);
         let workspace_root = workspace_root.to_owned();
 
         thread::spawn(move || {
             trace!("locking files");
             let (lock, cvar) = &*files_lock_clone;
             let mut workspace_files = lock.lock();
-            let files = nx_walker(workspace_root, |rec| {
-                let mut file_hashes: Vec<(PathBuf, String)> = vec![];
+
+            // Load the persisted index and reuse hashes for files whose mtime and
+            // size are unchanged; only the genuinely-changed files get rehashed.
+            // A fresh cache is built from the walk so deleted files fall out.
+            let previous = FileHashCache::load(&workspace_root);
+            let next_cache = Mutex::new(FileHashCache::empty());
+
+            let walk_root = workspace_root.clone();
+            let files = nx_walker(workspace_root.clone(), |rec| {
+                let mut file_hashes: Files = vec![];
                 for (path, content) in rec {
-                    file_hashes.push((path, hash(&content)));
+                    let relative = path.to_normalized_string();
+                    match std::fs::metadata(walk_root.join(&path)) {
+                        Ok(metadata) => {
+                            let hash = previous
+                                .get_valid(&relative, &metadata)
+                                .unwrap_or_else(|| hash(&content));
+                            next_cache
+                                .lock()
+                                .insert(relative, &metadata, hash.clone());
+                            file_hashes.push((path, mtime_ns(&metadata), metadata.len(), hash));
+                        }
+                        Err(_) => file_hashes.push((path, 0, 0, hash(&content))),
+                    }
                 }
                 file_hashes
             });
@@ -62,26 +223,47 @@ impl FilesWorker {
             let files_len = workspace_files.len();
             trace!(?files_len, "files retrieved");
 
+            next_cache.into_inner().save(&walk_root);
+
+            // Publish readiness before waking anyone: waiters loop on this flag
+            // so the store is guaranteed fully populated when they proceed.
+            initialized_clone.store(true, Ordering::Release);
             cvar.notify_all();
         });
 
-        FilesWorker(Some(files_lock))
+        FilesWorker {
+            files: Some(files_lock),
+            generation,
+            initialized,
+        }
+    }
+
+    /// Block until the initial `gather_files` walk has finished populating the
+    /// store. Used to serialize watcher-driven updates behind the first walk so
+    /// a batch can't drain an empty `Vec` that the walk then appends on top of.
+    fn wait_until_ready(&self) {
+        if let Some(files_sync) = &self.files {
+            let (files_lock, cvar) = files_sync.deref();
+            let mut files = files_lock.lock();
+            while !self.initialized.load(Ordering::Acquire) {
+                cvar.wait(&mut files);
+            }
+        }
     }
 
     pub fn get_files(&self) -> Vec<FileData> {
-        if let Some(files_sync) = &self.0 {
+        if let Some(files_sync) = &self.files {
             let (files_lock, cvar) = files_sync.deref();
             trace!("locking files");
             let mut files = files_lock.lock();
-            let files_len = files.len();
-            if files_len == 0 {
+            while !self.initialized.load(Ordering::Acquire) {
                 trace!("waiting for files");
                 cvar.wait(&mut files);
             }
 
             let file_data = files
                 .iter()
-                .map(|(path, hash)| FileData {
+                .map(|(path, _, _, hash)| FileData {
                     file: path.to_normalized_string(),
                     hash: hash.clone(),
                 })
@@ -102,42 +284,94 @@ impl FilesWorker {
         updated_files: Vec<&str>,
         deleted_files: Vec<&str>,
     ) -> HashMap<String, String> {
-        let Some(files_sync) = &self.0 else {
+        let Some(files_sync) = &self.files else {
             trace!("there were no files because the workspace root did not exist");
             return HashMap::new();
         };
 
         let (files_lock, _) = &files_sync.deref();
         let mut files = files_lock.lock();
-        let mut map: HashMap<PathBuf, String> = files.drain(..).collect();
+        // Keep the metadata sidecar around so unchanged files can reuse their
+        // hash without a read. Keyed by path for O(1) lookup during this batch.
+        let mut map: HashMap<PathBuf, (u128, u64, String)> = files
+            .drain(..)
+            .map(|(path, mtime_ns, size, hash)| (path, (mtime_ns, size, hash)))
+            .collect();
 
-        for deleted_file in deleted_files {
+        for deleted_file in &deleted_files {
             map.remove(&PathBuf::from(deleted_file));
         }
 
-        let updated_files_hashes: HashMap<String, String> = updated_files
+        // Stat each reported file first; reuse the stored hash when mtime and
+        // size are unchanged, and only read + rehash the genuinely-changed ones.
+        let mut reused: Vec<(String, u128, u64, String)> = vec![];
+        let mut to_hash: Vec<(String, u128, u64)> = vec![];
+        for path in updated_files {
+            let full_path = workspace_root_path.join(path);
+            let Ok(metadata) = std::fs::metadata(&full_path) else {
+                trace!("could not stat file: {full_path:?}");
+                continue;
+            };
+            let (mtime, size) = (mtime_ns(&metadata), metadata.len());
+            match map.get(&PathBuf::from(path)) {
+                Some((prev_mtime, prev_size, hash)) if *prev_mtime == mtime && *prev_size == size => {
+                    reused.push((path.to_string(), mtime, size, hash.clone()));
+                }
+                _ => to_hash.push((path.to_string(), mtime, size)),
+            }
+        }
+
+        let hashed: Vec<(String, u128, u64, String)> = to_hash
             .par_iter()
-            .filter_map(|path| {
+            .filter_map(|(path, mtime, size)| {
                 let full_path = workspace_root_path.join(path);
                 let Ok(content) = std::fs::read(&full_path) else {
                     trace!("could not read file: {full_path:?}");
                     return None;
                 };
-                Some((path.to_string(), hash(&content)))
+                Some((path.clone(), *mtime, *size, hash(&content)))
             })
             .collect();
 
-        for (file, hash) in &updated_files_hashes {
-            map.entry(file.into())
-                .and_modify(|e| *e = hash.clone())
-                .or_insert(hash.clone());
+        // The public contract with JS is only the changed `path -> hash` map;
+        // metadata stays internal. Reused entries are included so callers still
+        // learn the current hash of every file they asked about.
+        let updated_files_hashes: HashMap<String, String> = reused
+            .iter()
+            .chain(hashed.iter())
+            .map(|(path, _, _, hash)| (path.clone(), hash.clone()))
+            .collect();
+
+        for (file, mtime, size, hash) in reused.into_iter().chain(hashed.into_iter()) {
+            map.insert(PathBuf::from(file), (mtime, size, hash));
         }
 
-        *files = map.into_iter().collect();
+        *files = map
+            .into_iter()
+            .map(|(path, (mtime, size, hash))| (path, mtime, size, hash))
+            .collect();
         files.par_sort();
 
+        // Keep the on-disk index in step with this batch so the next cold start
+        // still benefits from the stat-only fast path.
+        let mut cache = FileHashCache::load(workspace_root_path);
+        for deleted_file in deleted_files {
+            cache.records.remove(deleted_file);
+        }
+        for (file, hash) in &updated_files_hashes {
+            if let Ok(metadata) = std::fs::metadata(workspace_root_path.join(file)) {
+                cache.insert(file.clone(), &metadata, hash.clone());
+            }
+        }
+        cache.save(workspace_root_path);
+
+        self.generation.fetch_add(1, Ordering::Relaxed);
         updated_files_hashes
     }
+
+    fn generation(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.generation)
+    }
 }
 
 #[napi]
@@ -154,7 +388,150 @@ impl WorkspaceContext {
             files_worker: FilesWorker::gather_files(&workspace_root_path),
             workspace_root,
             workspace_root_path,
+            watcher: Mutex::new(None),
+            overlay: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Layer `content` over `path` so queries see the in-flight buffer instead
+    /// of whatever is on disk (or surface a file that does not exist on disk
+    /// yet). Overlays win over both disk state and concurrent watcher updates
+    /// for the same path until explicitly cleared.
+    #[napi]
+    pub fn set_overlay(&self, path: String, content: Buffer) {
+        self.overlay
+            .lock()
+            .insert(PathBuf::from(path).to_normalized_string(), content.to_vec());
+        self.files_worker.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Drop the overlay for `path`; the file reverts to its on-disk hash, or
+    /// disappears from the file map if it has no on-disk backing.
+    #[napi]
+    pub fn clear_overlay(&self, path: String) {
+        self.overlay
+            .lock()
+            .remove(&PathBuf::from(path).to_normalized_string());
+        self.files_worker.generation.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Merge the overlay on top of a base file-data view, replacing the hash of
+    /// overlaid paths and appending overlay-only paths, then re-establishing the
+    /// sorted order callers rely on.
+    fn apply_overlay(&self, mut base: Vec<FileData>) -> Vec<FileData> {
+        let overlay = self.overlay.lock();
+        if overlay.is_empty() {
+            return base;
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for file in base.iter_mut() {
+            if let Some(content) = overlay.get(&file.file) {
+                file.hash = hash(content);
+                seen.insert(file.file.clone());
+            }
         }
+        for (path, content) in overlay.iter() {
+            if !seen.contains(path) {
+                base.push(FileData {
+                    file: path.clone(),
+                    hash: hash(content),
+                });
+            }
+        }
+
+        base.sort_by(|a, b| Path::new(&a.file).cmp(Path::new(&b.file)));
+        base
+    }
+
+    /// Spawn a recursive `notify` watcher over the workspace root and drive the
+    /// existing `update_files` logic as files change on disk, so the in-memory
+    /// `Files` store stays current without JS having to push updates in.
+    ///
+    /// `callback` is invoked once per debounced batch with the map of changed
+    /// `path -> hash` and the list of deleted paths. The watcher handle lives on
+    /// the context and is torn down on drop.
+    #[napi]
+    pub fn start_watching(
+        &self,
+        #[napi(ts_arg_type = "(updated: Record<string, string>, deleted: string[]) => void")]
+        callback: JsFunction,
+    ) -> napi::Result<()> {
+        let callback: ThreadsafeFunction<
+            (HashMap<String, String>, Vec<String>),
+            ErrorStrategy::Fatal,
+        > = callback.create_threadsafe_function(0, |ctx| {
+            let (updated, deleted): (HashMap<String, String>, Vec<String>) = ctx.value;
+            let mut updated_obj = ctx.env.create_object()?;
+            for (path, hash) in updated {
+                updated_obj.set(&path, hash)?;
+            }
+            Ok(vec![
+                updated_obj.into_unknown(),
+                ctx.env.to_js_value(&deleted)?,
+            ])
+        })?;
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            if let Ok(event) = res {
+                // Ignore send errors: they only mean the drain thread has exited
+                // because the watcher is being dropped.
+                let _ = tx.send(event);
+            }
+        })
+        .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+
+        watcher
+            .watch(&self.workspace_root_path, RecursiveMode::Recursive)
+            .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+
+        let files_worker = self.files_worker.clone();
+        let workspace_root_path = self.workspace_root_path.clone();
+        thread::spawn(move || {
+            // Serialize behind the initial walk: until it has populated the
+            // store, a batch would drain an empty `Vec` that the walk then
+            // appends on top of, duplicating entries.
+            files_worker.wait_until_ready();
+            loop {
+                // Block until the first event of a batch, then coalesce everything
+                // that lands within the debounce window into the same batch.
+                let Ok(first) = rx.recv() else {
+                    trace!("watcher channel closed, stopping drain thread");
+                    break;
+                };
+                let mut events = vec![first];
+                while let Ok(next) = rx.recv_timeout(WATCH_DEBOUNCE) {
+                    events.push(next);
+                }
+
+                // Rebuild the ignore matcher each batch so edits to
+                // `.gitignore`/`.nxignore` take effect without a restart.
+                let ignore = build_ignore(&workspace_root_path);
+                let (updated, deleted) = classify_events(
+                    &workspace_root_path,
+                    &ignore,
+                    events.iter().flat_map(|e| e.paths.iter().cloned()),
+                );
+
+                if updated.is_empty() && deleted.is_empty() {
+                    continue;
+                }
+
+                let updated_refs: Vec<&str> = updated.iter().map(|s| s.as_str()).collect();
+                let deleted_refs: Vec<&str> = deleted.iter().map(|s| s.as_str()).collect();
+                let changed =
+                    files_worker.update_files(&workspace_root_path, updated_refs, deleted_refs);
+
+                callback.call(
+                    (changed, deleted.clone()),
+                    ThreadsafeFunctionCallMode::NonBlocking,
+                );
+            }
+        });
+
+        *self.watcher.lock() = Some(watcher);
+        Ok(())
     }
 
     #[napi(ts_return_type = "Promise<NxWorkspaceFiles>")]
@@ -313,6 +690,136 @@ impl WorkspaceContext {
 
     #[napi]
     pub fn all_file_data(&self) -> Vec<FileData> {
-        self.files_worker.get_files()
+        self.apply_overlay(self.files_worker.get_files())
+    }
+
+    /// Hand out an immutable, consistent view of the workspace file map. The
+    /// returned handle pins the current `Vec<FileData>` behind an `Arc` (cheap to
+    /// clone, no rehash) so a caller can run `glob`, `hash_files_matching_glob`
+    /// and friends against a single "tick" even while a watcher or
+    /// `incremental_update` mutates the live state underneath it.
+    #[napi]
+    pub fn snapshot(&self) -> WorkspaceFilesSnapshot {
+        // Read the generation before the data: a mutation landing in between
+        // then makes the snapshot report stale rather than hide the change.
+        let generation = self.files_worker.generation.load(Ordering::Relaxed);
+        WorkspaceFilesSnapshot {
+            files: Arc::new(self.all_file_data()),
+            generation,
+            current_generation: self.files_worker.generation(),
+        }
+    }
+}
+
+/// An immutable snapshot of the workspace file map, à la rust-analyzer's
+/// `WorldSnapshot`. Queries run against the pinned data regardless of later
+/// mutations; `is_stale` reports whether the live state has since advanced.
+#[napi]
+pub struct WorkspaceFilesSnapshot {
+    files: Arc<Vec<FileData>>,
+    generation: u64,
+    current_generation: Arc<AtomicU64>,
+}
+
+#[napi]
+impl WorkspaceFilesSnapshot {
+    /// Whether the workspace has been mutated since this snapshot was taken.
+    #[napi]
+    pub fn is_stale(&self) -> bool {
+        self.current_generation.load(Ordering::Relaxed) != self.generation
+    }
+
+    #[napi]
+    pub fn all_file_data(&self) -> Vec<FileData> {
+        self.files.as_ref().clone()
+    }
+
+    #[napi]
+    pub fn glob(
+        &self,
+        globs: Vec<String>,
+        exclude: Option<Vec<String>>,
+    ) -> napi::Result<Vec<String>> {
+        let globbed_files = config_files::glob_files(&self.files, globs, exclude)?;
+        Ok(globbed_files.map(|file| file.file.to_owned()).collect())
     }
+
+    #[napi]
+    pub fn hash_files_matching_glob(
+        &self,
+        globs: Vec<String>,
+        exclude: Option<Vec<String>>,
+    ) -> napi::Result<String> {
+        let globbed_files = config_files::glob_files(&self.files, globs, exclude)?;
+        Ok(hash(
+            &globbed_files
+                .map(|file| file.hash.as_bytes())
+                .collect::<Vec<_>>()
+                .concat(),
+        ))
+    }
+}
+
+/// Turn a stream of raw event paths into workspace-relative `(updated, deleted)`
+/// lists. Paths outside the workspace or inside ignored directories are dropped
+/// before hashing; rename events naturally collapse into a delete of the old
+/// path and a create of the new one because classification is driven by whether
+/// the path still exists on disk.
+fn classify_events(
+    workspace_root_path: &Path,
+    ignore: &Gitignore,
+    paths: impl Iterator<Item = PathBuf>,
+) -> (Vec<String>, Vec<String>) {
+    let mut updated = vec![];
+    let mut deleted = vec![];
+    for path in paths {
+        let Ok(relative) = path.strip_prefix(workspace_root_path) else {
+            continue;
+        };
+        let exists = path.exists();
+        // A deleted path no longer exists, so `is_dir()` is always false and a
+        // removed directory would be matched as a file — test both dir and file
+        // forms so directory-only ignore rules (`dist/`) still catch it.
+        let ignored = if exists {
+            is_ignored_path(ignore, relative, path.is_dir())
+        } else {
+            is_ignored_path(ignore, relative, true) || is_ignored_path(ignore, relative, false)
+        };
+        if ignored {
+            continue;
+        }
+        let relative = relative.to_normalized_string();
+        if exists {
+            if !updated.contains(&relative) {
+                updated.push(relative);
+            }
+        } else if !deleted.contains(&relative) {
+            deleted.push(relative);
+        }
+    }
+    (updated, deleted)
+}
+
+/// Build the ignore matcher the watcher shares with `nx_walker`: the workspace
+/// `.gitignore` and `.nxignore`, plus `.git` and our own `.nx/cache` — the
+/// latter is written by `update_files` itself, so without this the save would
+/// re-fire as an event and spin an infinite feedback loop.
+fn build_ignore(workspace_root_path: &Path) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(workspace_root_path);
+    let _ = builder.add(workspace_root_path.join(".gitignore"));
+    let _ = builder.add(workspace_root_path.join(".nxignore"));
+    let _ = builder.add_line(None, ".git/");
+    let _ = builder.add_line(None, ".nx/cache/");
+    builder
+        .build()
+        .unwrap_or_else(|_| Gitignore::empty())
+}
+
+/// Run a candidate workspace-relative path through the same ignore rules
+/// `nx_walker` applies, so the watcher never injects files the initial walk
+/// deliberately excluded (`dist/`, `coverage/`, build/log output, `.env`, …).
+fn is_ignored_path(ignore: &Gitignore, relative: &Path, is_dir: bool) -> bool {
+    ignore
+        .matched_path_or_any_parents(relative, is_dir)
+        .is_ignore()
 }